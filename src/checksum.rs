@@ -0,0 +1,44 @@
+use bevy::prelude::*;
+use std::hash::{Hash, Hasher};
+
+use crate::round::{Player, Velocity};
+
+/// A per-entity checksum derived from the synced transform and velocity, used by
+/// GGRS to flag desyncs: two peers that ran the same confirmed inputs must end up
+/// with identical checksums every frame.
+#[derive(Default, Reflect, Hash, Component)]
+#[reflect(Hash)]
+pub struct Checksum {
+    pub value: u64,
+}
+
+pub fn checksum_players(mut query: Query<(&Transform, &Velocity, &mut Checksum), With<Player>>) {
+    for (transform, velocity, mut checksum) in query.iter_mut() {
+        let mut hasher = bevy::utils::AHasher::default();
+        transform.translation.x.to_bits().hash(&mut hasher);
+        transform.translation.y.to_bits().hash(&mut hasher);
+        velocity.0.x.to_bits().hash(&mut hasher);
+        velocity.0.y.to_bits().hash(&mut hasher);
+        checksum.value = hasher.finish();
+    }
+}
+
+/// Folds every player's checksum into one value representative of the whole
+/// simulation. Desync detection and replay verification both need this
+/// instead of an arbitrary single player's checksum, so that a desync
+/// affecting only one non-local player handle is still caught. Players are
+/// folded in handle order so every peer combines them identically.
+pub fn combined_checksum(checksums: &Query<(&Player, &Checksum)>) -> Option<u64> {
+    let mut by_handle: Vec<_> = checksums.iter().collect();
+    if by_handle.is_empty() {
+        return None;
+    }
+    by_handle.sort_by_key(|(player, _)| player.handle);
+
+    let mut combined = 0xcbf29ce484222325_u64; // FNV-1a offset basis
+    for (_, checksum) in by_handle {
+        combined ^= checksum.value;
+        combined = combined.wrapping_mul(0x100000001b3); // FNV-1a prime
+    }
+    Some(combined)
+}
@@ -0,0 +1,129 @@
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+use bevy_ggrs::Session;
+use matchbox_socket::PeerId;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    checksum::{combined_checksum, Checksum},
+    menu::connect::{SideChannelSocket, SIDE_CHANNEL},
+    round::{FrameCount, Player},
+    settings::SessionSettings,
+    AppState, GGRSConfig,
+};
+
+#[derive(Serialize, Deserialize)]
+struct ChecksumMessage {
+    frame: u32,
+    checksum: u64,
+}
+
+pub struct DesyncEvent {
+    pub frame: u32,
+    pub local: u64,
+    pub remote: u64,
+    pub peer: PeerId,
+}
+
+/// Checksums peers have reported, keyed by frame number. Each frame holds only
+/// the most recently received report; an earlier peer's report for the same
+/// frame is overwritten. Entries stay after they are compared until
+/// `detect_desync` prunes them.
+#[derive(Default)]
+pub struct RemoteChecksums(pub HashMap<u32, (PeerId, u64)>);
+
+/// Broadcasts our confirmed-frame checksum to every peer every
+/// `settings.check_distance` frames over the side channel, cheap and infrequent enough to not compete
+/// with GGRS's own input traffic on the main channel. Lives in the
+/// `CHECKSUM_UPDATE` rollback stage (see `main.rs`), right alongside
+/// `checksum_players`, so it observes every confirmed simulated frame instead
+/// of only whichever frame happens to be the frontier once per engine tick.
+pub fn broadcast_checksum(
+    mut socket: ResMut<SideChannelSocket>,
+    frame_count: Res<FrameCount>,
+    session: Res<Session<GGRSConfig>>,
+    checksums: Query<(&Player, &Checksum)>,
+    settings: Res<SessionSettings>,
+) {
+    if frame_count.frame as usize % settings.check_distance != 0 {
+        return;
+    }
+
+    let Session::P2PSession(ggrs_session) = session.as_ref() else {
+        return;
+    };
+    let confirmed_frame = ggrs_session.confirmed_frame();
+    if confirmed_frame < 0 || frame_count.frame as i32 != confirmed_frame {
+        return;
+    }
+
+    let Some(checksum) = combined_checksum(&checksums) else {
+        return;
+    };
+    let message = ChecksumMessage {
+        frame: frame_count.frame,
+        checksum,
+    };
+    let Ok(packet) = bincode::serialize(&message) else {
+        return;
+    };
+    let packet = packet.into_boxed_slice();
+
+    for peer in socket.0.connected_peers(SIDE_CHANNEL) {
+        socket.0.channel(SIDE_CHANNEL).send(packet.clone(), peer);
+    }
+}
+
+pub fn receive_remote_checksums(
+    mut socket: ResMut<SideChannelSocket>,
+    mut remote: ResMut<RemoteChecksums>,
+) {
+    for (peer, packet) in socket.0.channel(SIDE_CHANNEL).receive() {
+        if let Ok(message) = bincode::deserialize::<ChecksumMessage>(&packet) {
+            remote.0.insert(message.frame, (peer, message.checksum));
+        }
+    }
+}
+
+/// Compares our confirmed-frame checksum against the latest checksum a peer
+/// has reported for that same frame (see [`RemoteChecksums`]). Both sides only ever report confirmed checksums, so a
+/// mismatch here is a genuine desync rather than a prediction artifact. Lives
+/// in the `CHECKSUM_UPDATE` rollback stage alongside `broadcast_checksum`, so
+/// the `frame_count.frame == confirmed_frame` gate is actually reachable
+/// instead of comparing whatever frame happens to be the frontier once a tick.
+pub fn detect_desync(
+    frame_count: Res<FrameCount>,
+    session: Res<Session<GGRSConfig>>,
+    checksums: Query<(&Player, &Checksum)>,
+    mut remote: ResMut<RemoteChecksums>,
+    mut events: EventWriter<DesyncEvent>,
+    mut state: ResMut<State<AppState>>,
+    settings: Res<SessionSettings>,
+) {
+    let Session::P2PSession(ggrs_session) = session.as_ref() else {
+        return;
+    };
+    let confirmed_frame = ggrs_session.confirmed_frame();
+
+    if confirmed_frame >= 0 && frame_count.frame as i32 == confirmed_frame {
+        if let Some(local_checksum) = combined_checksum(&checksums) {
+            if let Some(&(peer, remote_checksum)) = remote.0.get(&(confirmed_frame as u32)) {
+                if remote_checksum != local_checksum {
+                    events.send(DesyncEvent {
+                        frame: confirmed_frame as u32,
+                        local: local_checksum,
+                        remote: remote_checksum,
+                        peer,
+                    });
+                    let _ = state.set(AppState::Desync);
+                }
+            }
+        }
+    }
+
+    let cutoff = frame_count
+        .frame
+        .saturating_sub((settings.max_prediction + settings.check_distance) as u32);
+    remote.0.retain(|&frame, _| frame >= cutoff);
+}
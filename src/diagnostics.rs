@@ -0,0 +1,146 @@
+use std::collections::VecDeque;
+
+use bevy::diagnostic::{Diagnostics, FrameTimeDiagnosticsPlugin};
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContext};
+use bevy_ggrs::Session;
+
+use crate::{round::FrameCount, settings::SessionSettings, GGRSConfig};
+
+pub const TOGGLE_KEY: KeyCode = KeyCode::F3;
+const ROLLING_WINDOW_SECS: f64 = 1.0;
+const FRAME_TIME_SAMPLES: usize = 120;
+
+#[derive(Default)]
+pub struct DiagnosticsOverlay {
+    pub visible: bool,
+    pub frame_time_history: VecDeque<f32>,
+}
+
+/// Tracks rollback activity by watching `FrameCount`, a rollback-registered
+/// resource that GGRS rewinds and resimulates in place. This resource is
+/// deliberately *not* rollback-registered, so it keeps its history across a
+/// resimulation instead of being rewound along with it.
+#[derive(Default)]
+pub struct RollbackStats {
+    max_frame_seen: Option<u32>,
+    samples: VecDeque<(f64, bool)>,
+}
+
+impl RollbackStats {
+    pub fn rollbacks_last_second(&self) -> usize {
+        let mut count = 0;
+        let mut prev_replay = false;
+        for &(_, replay) in &self.samples {
+            if replay && !prev_replay {
+                count += 1;
+            }
+            prev_replay = replay;
+        }
+        count
+    }
+
+    pub fn rolled_back_frames_last_second(&self) -> usize {
+        self.samples.iter().filter(|&&(_, replay)| replay).count()
+    }
+}
+
+/// Appended to the `CHECKSUM_UPDATE` stage so it observes every resimulation,
+/// not just the final frame of a rollback.
+pub fn track_rollback_stats(
+    frame_count: Res<FrameCount>,
+    time: Res<Time>,
+    mut stats: ResMut<RollbackStats>,
+) {
+    let now = time.seconds_since_startup();
+    // Every frame up to the highest one we'd already reached is a resimulated
+    // frame, not just the single sample where the count first regresses.
+    let was_replay = matches!(stats.max_frame_seen, Some(max) if frame_count.frame <= max);
+    stats.max_frame_seen = Some(match stats.max_frame_seen {
+        Some(max) => max.max(frame_count.frame),
+        None => frame_count.frame,
+    });
+    stats.samples.push_back((now, was_replay));
+
+    let cutoff = now - ROLLING_WINDOW_SECS;
+    while stats.samples.front().map_or(false, |&(t, _)| t < cutoff) {
+        stats.samples.pop_front();
+    }
+}
+
+pub fn toggle_overlay(keys: Res<Input<KeyCode>>, mut overlay: ResMut<DiagnosticsOverlay>) {
+    if keys.just_pressed(TOGGLE_KEY) {
+        overlay.visible = !overlay.visible;
+    }
+}
+
+pub fn track_frame_time(diagnostics: Res<Diagnostics>, mut overlay: ResMut<DiagnosticsOverlay>) {
+    let Some(frame_time) = diagnostics
+        .get(FrameTimeDiagnosticsPlugin::FRAME_TIME)
+        .and_then(|d| d.value())
+    else {
+        return;
+    };
+
+    // the diagnostic is reported in seconds; the graph is plotted in ms
+    overlay.frame_time_history.push_back(frame_time as f32 * 1000.);
+    if overlay.frame_time_history.len() > FRAME_TIME_SAMPLES {
+        overlay.frame_time_history.pop_front();
+    }
+}
+
+pub fn render_overlay(
+    mut egui_ctx: ResMut<EguiContext>,
+    overlay: Res<DiagnosticsOverlay>,
+    rollback_stats: Res<RollbackStats>,
+    frame_count: Res<FrameCount>,
+    session: Res<Session<GGRSConfig>>,
+    settings: Res<SessionSettings>,
+) {
+    if !overlay.visible {
+        return;
+    }
+
+    let Session::P2PSession(ggrs_session) = session.as_ref() else {
+        return;
+    };
+
+    egui::Window::new("Netcode Diagnostics").show(egui_ctx.ctx_mut(), |ui| {
+        let confirmed_frame = ggrs_session.confirmed_frame();
+        let prediction_gap = (frame_count.frame as i32 - confirmed_frame).max(0);
+
+        ui.label(format!("frame: {}", frame_count.frame));
+        ui.label(format!("confirmed frame: {confirmed_frame}"));
+        ui.label(format!(
+            "predicted/confirmed gap: {prediction_gap} (max {})",
+            settings.max_prediction
+        ));
+        ui.label(format!(
+            "rollbacks/s: {}, rolled-back frames/s: {}",
+            rollback_stats.rollbacks_last_second(),
+            rollback_stats.rolled_back_frames_last_second()
+        ));
+
+        ui.separator();
+        for handle in 0..ggrs_session.num_players() {
+            if let Ok(stats) = ggrs_session.network_stats(handle) {
+                ui.label(format!(
+                    "peer {handle}: {}ms ping, {:.1} kbps",
+                    stats.ping, stats.kbps_sent
+                ));
+            }
+        }
+
+        ui.separator();
+        ui.label("frame time (ms)");
+        let points: egui::plot::PlotPoints = overlay
+            .frame_time_history
+            .iter()
+            .enumerate()
+            .map(|(i, ms)| [i as f64, *ms as f64])
+            .collect();
+        egui::plot::Plot::new("frame_time_plot")
+            .height(80.)
+            .show(ui, |plot_ui| plot_ui.line(egui::plot::Line::new(points)));
+    });
+}
@@ -0,0 +1,68 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContext};
+use bevy_ggrs::Session;
+use ggrs::GgrsEvent;
+
+use crate::{AppState, GGRSConfig};
+
+/// Set while GGRS reports the peer as `NetworkInterrupted` and cleared again on
+/// `NetworkResumed`, on `Disconnected`, or when the online round is exited.
+/// Drives the "waiting for peer" overlay instead of just a log line.
+#[derive(Default)]
+pub struct PeerConnectionStatus {
+    pub interrupted: bool,
+}
+
+/// Maps each GGRS P2P event to real behavior instead of a `println`:
+/// `NetworkInterrupted`/`NetworkResumed` flip the "waiting for peer" overlay,
+/// `Disconnected` sends us to [`AppState::PeerLost`] so the player can back out
+/// or try a rematch. Every other variant is just logged, as `print_p2p_events`
+/// used to do for all of them.
+pub fn handle_p2p_events(
+    mut session: ResMut<Session<GGRSConfig>>,
+    mut status: ResMut<PeerConnectionStatus>,
+    mut state: ResMut<State<AppState>>,
+) {
+    let Session::P2PSession(ggrs_session) = session.as_mut() else {
+        return;
+    };
+
+    for event in ggrs_session.events() {
+        match event {
+            GgrsEvent::NetworkInterrupted { addr, .. } => {
+                info!("peer {addr} interrupted, waiting for it to resume");
+                status.interrupted = true;
+            }
+            GgrsEvent::NetworkResumed { addr } => {
+                info!("peer {addr} resumed");
+                status.interrupted = false;
+            }
+            GgrsEvent::Disconnected { addr } => {
+                info!("peer {addr} disconnected");
+                status.interrupted = false;
+                let _ = state.set(AppState::PeerLost);
+            }
+            other => info!("GGRS event: {other:?}"),
+        }
+    }
+}
+
+pub fn reset_connection_status(mut status: ResMut<PeerConnectionStatus>) {
+    status.interrupted = false;
+}
+
+pub fn render_interrupted_overlay(
+    mut egui_ctx: ResMut<EguiContext>,
+    status: Res<PeerConnectionStatus>,
+) {
+    if !status.interrupted {
+        return;
+    }
+
+    egui::Window::new("waiting_for_peer")
+        .title_bar(false)
+        .anchor(egui::Align2::CENTER_TOP, [0., 20.])
+        .show(egui_ctx.ctx_mut(), |ui| {
+            ui.label("Waiting for peer to reconnect...");
+        });
+}
@@ -1,27 +1,41 @@
 mod checksum;
+mod desync;
+mod diagnostics;
+mod disconnect;
 mod menu;
+mod replay;
 mod round;
+mod settings;
 
 #[cfg(target_arch = "wasm32")]
 use approx::relative_eq;
+use bevy::diagnostic::FrameTimeDiagnosticsPlugin;
 use bevy::prelude::*;
 use bevy_asset_loader::{AssetCollection, AssetLoader};
+use bevy_egui::EguiPlugin;
 use bevy_ggrs::GGRSPlugin;
 use checksum::{checksum_players, Checksum};
+use desync::{broadcast_checksum, detect_desync, receive_remote_checksums, DesyncEvent, RemoteChecksums};
+use diagnostics::{
+    render_overlay, toggle_overlay, track_frame_time, track_rollback_stats, DiagnosticsOverlay,
+    RollbackStats,
+};
+use disconnect::{handle_p2p_events, render_interrupted_overlay, reset_connection_status, PeerConnectionStatus};
 use ggrs::Config;
-use menu::connect::{create_matchbox_socket, update_matchbox_socket};
+use menu::connect::{create_matchbox_socket, update_matchbox_socket, LastRoom};
+use menu::desync::{record_last_desync, LastDesync};
+use replay::{
+    advance_or_finish_replay, load_replay_log, record_frame, replay_apply_inputs,
+    reset_replay_log, save_replay_log, verify_replay_checksum, ReplayCursor, ReplayLog,
+};
 use round::{
     apply_inputs, check_win, cleanup_round, increase_frame_count, move_players, print_p2p_events,
     setup_round, spawn_players, update_velocity, FrameCount, Velocity,
 };
+use settings::SessionSettings;
 
-const NUM_PLAYERS: usize = 2;
-const FPS: usize = 60;
 const ROLLBACK_SYSTEMS: &str = "rollback_systems";
 const CHECKSUM_UPDATE: &str = "checksum_update";
-const MAX_PREDICTION: usize = 12;
-const INPUT_DELAY: usize = 2;
-const CHECK_DISTANCE: usize = 2;
 
 const NORMAL_BUTTON: Color = Color::rgb(0.15, 0.15, 0.15);
 const HOVERED_BUTTON: Color = Color::rgb(0.25, 0.25, 0.25);
@@ -36,7 +50,12 @@ pub enum AppState {
     MenuConnect,
     RoundLocal,
     RoundOnline,
+    RoundSpectate,
+    Replay,
+    Desync,
+    MenuSettings,
     Win,
+    PeerLost,
 }
 
 #[derive(SystemLabel, Debug, Clone, Hash, Eq, PartialEq)]
@@ -68,6 +87,11 @@ impl Config for GGRSConfig {
 fn main() {
     let mut app = App::new();
 
+    // Read once, synchronously, before the app (and its fixed-at-build-time
+    // GGRSPlugin schedule) exist: a changed `fps` only takes effect on the
+    // game's next launch, see `SessionSettings`.
+    let settings = SessionSettings::load();
+
     AssetLoader::new(AppState::AssetLoading)
         .continue_to_state(AppState::MenuMain)
         .with_collection::<ImageAssets>()
@@ -75,7 +99,7 @@ fn main() {
         .build(&mut app);
 
     GGRSPlugin::<GGRSConfig>::new()
-        .with_update_frequency(FPS)
+        .with_update_frequency(settings.fps)
         .with_input_system(round::input)
         .register_rollback_type::<Transform>()
         .register_rollback_type::<Velocity>()
@@ -98,12 +122,29 @@ fn main() {
                 .with_stage_after(
                     ROLLBACK_SYSTEMS,
                     CHECKSUM_UPDATE,
-                    SystemStage::parallel().with_system(checksum_players),
+                    SystemStage::parallel()
+                        .with_system(checksum_players)
+                        .with_system(record_frame.after(checksum_players))
+                        .with_system(broadcast_checksum.after(checksum_players))
+                        .with_system(detect_desync.after(checksum_players))
+                        .with_system(track_rollback_stats),
                 ),
         )
         .build(&mut app);
 
-    app.add_plugins(DefaultPlugins)
+    app.insert_resource(settings)
+        .insert_resource(ReplayLog::default())
+        .insert_resource(ReplayCursor::default())
+        .insert_resource(RemoteChecksums::default())
+        .insert_resource(LastDesync::default())
+        .insert_resource(DiagnosticsOverlay::default())
+        .insert_resource(RollbackStats::default())
+        .insert_resource(LastRoom::default())
+        .insert_resource(PeerConnectionStatus::default())
+        .add_event::<DesyncEvent>()
+        .add_plugins(DefaultPlugins)
+        .add_plugin(FrameTimeDiagnosticsPlugin)
+        .add_plugin(EguiPlugin)
         .add_system(update_window_size)
         .add_state(AppState::AssetLoading)
         // main menu
@@ -114,6 +155,18 @@ fn main() {
                 .with_system(menu::main::btn_listeners),
         )
         .add_system_set(SystemSet::on_exit(AppState::MenuMain).with_system(menu::main::cleanup_ui))
+        // settings menu
+        .add_system_set(
+            SystemSet::on_enter(AppState::MenuSettings).with_system(menu::settings::setup_ui),
+        )
+        .add_system_set(
+            SystemSet::on_update(AppState::MenuSettings)
+                .with_system(menu::settings::btn_visuals)
+                .with_system(menu::settings::btn_listeners),
+        )
+        .add_system_set(
+            SystemSet::on_exit(AppState::MenuSettings).with_system(menu::settings::cleanup_ui),
+        )
         //online menu
         .add_system_set(
             SystemSet::on_enter(AppState::MenuOnline).with_system(menu::online::setup_ui),
@@ -161,14 +214,83 @@ fn main() {
         .add_system_set(
             SystemSet::on_enter(AppState::RoundOnline)
                 .with_system(setup_round)
-                .with_system(spawn_players),
+                .with_system(spawn_players)
+                .with_system(reset_replay_log),
         )
         .add_system_set(
             SystemSet::on_update(AppState::RoundOnline)
+                .with_system(handle_p2p_events)
+                .with_system(check_win)
+                .with_system(receive_remote_checksums)
+                .with_system(record_last_desync)
+                .with_system(toggle_overlay)
+                .with_system(track_frame_time)
+                .with_system(render_overlay)
+                .with_system(render_interrupted_overlay),
+        )
+        .add_system_set(
+            SystemSet::on_exit(AppState::RoundOnline)
+                .with_system(cleanup_round)
+                .with_system(save_replay_log)
+                .with_system(reset_connection_status),
+        )
+        // spectator round
+        .add_system_set(
+            SystemSet::on_enter(AppState::RoundSpectate)
+                .with_system(setup_round)
+                .with_system(spawn_players),
+        )
+        .add_system_set(
+            SystemSet::on_update(AppState::RoundSpectate)
                 .with_system(print_p2p_events)
                 .with_system(check_win),
         )
-        .add_system_set(SystemSet::on_exit(AppState::RoundOnline).with_system(cleanup_round))
+        .add_system_set(SystemSet::on_exit(AppState::RoundSpectate).with_system(cleanup_round))
+        // replay playback: re-runs the rollback schedule from a recorded log instead
+        // of a live GGRS session
+        .add_system_set(
+            SystemSet::on_enter(AppState::Replay)
+                .with_system(load_replay_log)
+                .with_system(setup_round)
+                .with_system(spawn_players),
+        )
+        .add_system_set(
+            SystemSet::on_update(AppState::Replay)
+                .with_system(replay_apply_inputs.label(SystemLabel::Input))
+                .with_system(
+                    update_velocity
+                        .label(SystemLabel::Velocity)
+                        .after(SystemLabel::Input),
+                )
+                .with_system(move_players.after(SystemLabel::Velocity))
+                .with_system(increase_frame_count.after(move_players))
+                .with_system(checksum_players.after(increase_frame_count))
+                .with_system(verify_replay_checksum.after(checksum_players))
+                .with_system(advance_or_finish_replay.after(verify_replay_checksum)),
+        )
+        .add_system_set(SystemSet::on_exit(AppState::Replay).with_system(cleanup_round))
+        // desync screen
+        .add_system_set(SystemSet::on_enter(AppState::Desync).with_system(menu::desync::setup_ui))
+        .add_system_set(
+            SystemSet::on_update(AppState::Desync)
+                .with_system(menu::desync::btn_visuals)
+                .with_system(menu::desync::btn_listeners),
+        )
+        .add_system_set(
+            SystemSet::on_exit(AppState::Desync).with_system(menu::desync::cleanup_ui),
+        )
+        // peer lost screen: reached when GGRS reports the remote peer as Disconnected
+        .add_system_set(
+            SystemSet::on_enter(AppState::PeerLost).with_system(menu::peer_lost::setup_ui),
+        )
+        .add_system_set(
+            SystemSet::on_update(AppState::PeerLost)
+                .with_system(menu::peer_lost::btn_visuals)
+                .with_system(menu::peer_lost::btn_listeners),
+        )
+        .add_system_set(
+            SystemSet::on_exit(AppState::PeerLost).with_system(menu::peer_lost::cleanup_ui),
+        )
         .run();
 }
 
@@ -0,0 +1,205 @@
+use bevy::{prelude::*, tasks::IoTaskPool};
+use bevy_ggrs::Session;
+use ggrs::SessionBuilder;
+use matchbox_socket::{ChannelConfig, PeerId, PlayerType, WebRtcSocket, WebRtcSocketBuilder};
+
+use crate::{menu::spawn_button, settings::SessionSettings, AppState, FontAssets, GGRSConfig};
+
+use super::MenuUI;
+
+/// Channel 0 carries GGRS's own input/sync traffic; channel 1 is a reliable
+/// side channel free for app-level messages (e.g. desync checksum exchange)
+/// that shouldn't compete with or be reordered alongside rollback input.
+const GGRS_CHANNEL: usize = 0;
+pub const SIDE_CHANNEL: usize = 1;
+
+#[derive(Component)]
+struct BackButton;
+
+pub struct MatchboxSocketWrapper(pub Option<WebRtcSocket>);
+
+/// What remains of the matchbox socket once its GGRS channel has been handed
+/// off to a `Session`: still-open side channels for app-level peer messages.
+pub struct SideChannelSocket(pub WebRtcSocket);
+
+/// The room id we last connected (or tried to connect) to, kept around so a
+/// rematch after [`AppState::PeerLost`](crate::AppState::PeerLost) can rejoin
+/// the same room instead of negotiating a brand new one.
+#[derive(Default)]
+pub struct LastRoom(pub Option<String>);
+
+/// `next=num_players` tells the matchbox server how many peers make up a full
+/// match; it keeps matching arrivals into rooms until that many have joined.
+/// Peers that arrive after a room's player slots are already taken fall
+/// straight through to [`update_matchbox_socket`]'s spectator branch instead of
+/// blocking the room for everyone else.
+pub fn create_matchbox_socket(
+    mut commands: Commands,
+    mut last_room: ResMut<LastRoom>,
+    settings: Res<SessionSettings>,
+) {
+    let num_players = settings.num_players;
+    let room_url = last_room
+        .0
+        .clone()
+        .unwrap_or_else(|| format!("ws://localhost:3536/extreme_bevy?next={num_players}"));
+    info!("connecting to matchbox server: {room_url}");
+    let (socket, message_loop) = WebRtcSocketBuilder::new(room_url.clone())
+        .add_channel(ChannelConfig::ggrs())
+        .add_channel(ChannelConfig::reliable())
+        .build();
+
+    IoTaskPool::get().spawn(message_loop).detach();
+
+    last_room.0 = Some(room_url);
+    commands.insert_resource(MatchboxSocketWrapper(Some(socket)));
+}
+
+pub fn update_matchbox_socket(
+    mut commands: Commands,
+    mut socket_res: ResMut<MatchboxSocketWrapper>,
+    mut state: ResMut<State<AppState>>,
+    settings: Res<SessionSettings>,
+) {
+    let socket = socket_res.0.as_mut().expect("socket already consumed");
+    socket.accept_new_connections();
+    let connected_peers = socket.players();
+
+    if connected_peers.len() < settings.num_players {
+        // still waiting for the player slots to fill
+        return;
+    }
+
+    // `players()` orders every joined peer (including us, as `PlayerType::Local`)
+    // the same way on every peer's socket, so our own position in it is a stable
+    // identity rather than a snapshot of however many peers happen to have
+    // joined by the time we look. A peer that lands within the first
+    // `num_players` slots plays; everyone after that watches. Deciding by our
+    // own index instead of `connected_peers.len()` means a spectator racing in
+    // alongside the last player slot filling can't flip either side's role.
+    let our_index = connected_peers
+        .iter()
+        .position(|player| matches!(player, PlayerType::Local))
+        .expect("we should always be one of the connected peers");
+
+    if our_index < settings.num_players {
+        // only the first `num_players` slots are players; anyone beyond that
+        // (already-connected spectators) must not be handed to the session
+        // builder, which assigns a handle to every entry it's given.
+        let players = connected_peers.into_iter().take(settings.num_players).collect();
+        start_p2p_session(&mut commands, &mut socket_res, &mut state, &settings, players);
+    } else {
+        start_spectator_session(&mut commands, &mut socket_res, &mut state, &settings, connected_peers);
+    }
+}
+
+fn start_p2p_session(
+    commands: &mut Commands,
+    socket_res: &mut MatchboxSocketWrapper,
+    state: &mut State<AppState>,
+    settings: &SessionSettings,
+    players: Vec<PlayerType<PeerId>>,
+) {
+    let mut session_builder = SessionBuilder::<GGRSConfig>::new()
+        .with_num_players(settings.num_players)
+        .with_max_prediction_window(settings.max_prediction);
+
+    for (i, player) in players.into_iter().enumerate() {
+        session_builder = session_builder
+            .add_player(player, i)
+            .expect("failed to add player to session");
+    }
+
+    let mut socket = socket_res.0.take().unwrap();
+    let ggrs_socket = socket.take_channel(GGRS_CHANNEL).unwrap();
+    let mut ggrs_session = session_builder
+        .start_p2p_session(ggrs_socket)
+        .expect("failed to start p2p session");
+
+    for handle in 0..settings.num_players {
+        let _ = ggrs_session.set_frame_delay(settings.input_delay, handle);
+    }
+
+    commands.insert_resource(Session::P2PSession(ggrs_session));
+    commands.insert_resource(SideChannelSocket(socket));
+    state.set(AppState::RoundOnline).unwrap();
+}
+
+fn start_spectator_session(
+    commands: &mut Commands,
+    socket_res: &mut MatchboxSocketWrapper,
+    state: &mut State<AppState>,
+    settings: &SessionSettings,
+    players: Vec<PlayerType<PeerId>>,
+) {
+    let host = players
+        .into_iter()
+        .find_map(|player| match player {
+            PlayerType::Remote(addr) => Some(addr),
+            PlayerType::Local | PlayerType::Spectator(_) => None,
+        })
+        .expect("a spectator needs at least one remote peer to receive inputs from");
+
+    let mut socket = socket_res.0.take().unwrap();
+    let ggrs_socket = socket.take_channel(GGRS_CHANNEL).unwrap();
+    let session_builder = SessionBuilder::<GGRSConfig>::new()
+        .with_num_players(settings.num_players)
+        .with_max_prediction_window(settings.max_prediction);
+    let spectator_session = session_builder.start_spectator_session(host, ggrs_socket);
+
+    commands.insert_resource(Session::SpectatorSession(spectator_session));
+    commands.insert_resource(SideChannelSocket(socket));
+    state.set(AppState::RoundSpectate).unwrap();
+}
+
+pub fn setup_ui(mut commands: Commands, fonts: Res<FontAssets>) {
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.), Val::Percent(100.)),
+                flex_direction: FlexDirection::ColumnReverse,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            color: Color::NONE.into(),
+            ..default()
+        })
+        .insert(MenuUI)
+        .with_children(|parent| {
+            parent.spawn_bundle(TextBundle::from_section(
+                "Waiting for opponent...",
+                TextStyle {
+                    font: fonts.default_font.clone(),
+                    font_size: 40.,
+                    color: crate::TEXT,
+                },
+            ));
+            spawn_button(parent, &fonts, "Cancel", BackButton);
+        });
+}
+
+pub fn btn_visuals(
+    query: Query<(&Interaction, &mut UiColor), (Changed<Interaction>, With<Button>)>,
+) {
+    super::btn_visuals_system(query);
+}
+
+pub fn btn_listeners(
+    mut commands: Commands,
+    mut state: ResMut<State<AppState>>,
+    query: Query<&Interaction, (Changed<Interaction>, With<BackButton>)>,
+) {
+    for interaction in query.iter() {
+        if *interaction == Interaction::Clicked {
+            commands.remove_resource::<MatchboxSocketWrapper>();
+            state.set(AppState::MenuOnline).unwrap();
+        }
+    }
+}
+
+pub fn cleanup_ui(query: Query<Entity, With<MenuUI>>, mut commands: Commands) {
+    for e in query.iter() {
+        commands.entity(e).despawn_recursive();
+    }
+}
@@ -0,0 +1,76 @@
+use bevy::prelude::*;
+
+use crate::{desync::DesyncEvent, menu::spawn_button, AppState, FontAssets};
+
+use super::MenuUI;
+
+#[derive(Component)]
+struct BackButton;
+
+/// Last [`DesyncEvent`] seen, kept around so the screen can show which frame
+/// and checksums disagreed instead of just "a desync happened".
+#[derive(Default)]
+pub struct LastDesync(pub Option<(u32, u64, u64)>);
+
+pub fn record_last_desync(mut events: EventReader<DesyncEvent>, mut last: ResMut<LastDesync>) {
+    if let Some(event) = events.iter().last() {
+        last.0 = Some((event.frame, event.local, event.remote));
+    }
+}
+
+pub fn setup_ui(mut commands: Commands, fonts: Res<FontAssets>, last: Res<LastDesync>) {
+    let message = match last.0 {
+        Some((frame, local, remote)) => format!(
+            "Desync detected at frame {frame}\nlocal checksum {local:#x} != remote {remote:#x}"
+        ),
+        None => "Desync detected".to_string(),
+    };
+
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.), Val::Percent(100.)),
+                flex_direction: FlexDirection::ColumnReverse,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            color: Color::NONE.into(),
+            ..default()
+        })
+        .insert(MenuUI)
+        .with_children(|parent| {
+            parent.spawn_bundle(TextBundle::from_section(
+                message,
+                TextStyle {
+                    font: fonts.default_font.clone(),
+                    font_size: 30.,
+                    color: crate::TEXT,
+                },
+            ));
+            spawn_button(parent, &fonts, "Main Menu", BackButton);
+        });
+}
+
+pub fn btn_visuals(
+    query: Query<(&Interaction, &mut UiColor), (Changed<Interaction>, With<Button>)>,
+) {
+    super::btn_visuals_system(query);
+}
+
+pub fn btn_listeners(
+    mut state: ResMut<State<AppState>>,
+    query: Query<&Interaction, (Changed<Interaction>, With<BackButton>)>,
+) {
+    for interaction in query.iter() {
+        if *interaction == Interaction::Clicked {
+            state.set(AppState::MenuMain).unwrap();
+        }
+    }
+}
+
+pub fn cleanup_ui(query: Query<Entity, With<MenuUI>>, mut commands: Commands) {
+    for e in query.iter() {
+        commands.entity(e).despawn_recursive();
+    }
+}
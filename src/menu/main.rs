@@ -0,0 +1,63 @@
+use bevy::prelude::*;
+
+use crate::{menu::spawn_button, AppState, FontAssets};
+
+use super::MenuUI;
+
+#[derive(Component)]
+enum MainButton {
+    Local,
+    Online,
+    Settings,
+    Replay,
+}
+
+pub fn setup_ui(mut commands: Commands, fonts: Res<FontAssets>) {
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.), Val::Percent(100.)),
+                flex_direction: FlexDirection::ColumnReverse,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            color: Color::NONE.into(),
+            ..default()
+        })
+        .insert(MenuUI)
+        .with_children(|parent| {
+            spawn_button(parent, &fonts, "Local", MainButton::Local);
+            spawn_button(parent, &fonts, "Online", MainButton::Online);
+            spawn_button(parent, &fonts, "Settings", MainButton::Settings);
+            spawn_button(parent, &fonts, "Watch Replay", MainButton::Replay);
+        });
+}
+
+pub fn btn_visuals(
+    query: Query<(&Interaction, &mut UiColor), (Changed<Interaction>, With<Button>)>,
+) {
+    super::btn_visuals_system(query);
+}
+
+pub fn btn_listeners(
+    mut state: ResMut<State<AppState>>,
+    query: Query<(&Interaction, &MainButton), Changed<Interaction>>,
+) {
+    for (interaction, button) in query.iter() {
+        if *interaction == Interaction::Clicked {
+            match button {
+                MainButton::Local => state.set(AppState::RoundLocal).unwrap(),
+                MainButton::Online => state.set(AppState::MenuOnline).unwrap(),
+                MainButton::Settings => state.set(AppState::MenuSettings).unwrap(),
+                MainButton::Replay => state.set(AppState::Replay).unwrap(),
+            }
+        }
+    }
+}
+
+pub fn cleanup_ui(query: Query<Entity, With<MenuUI>>, mut commands: Commands) {
+    for e in query.iter() {
+        commands.entity(e).despawn_recursive();
+    }
+}
@@ -0,0 +1,57 @@
+pub mod connect;
+pub mod desync;
+pub mod main;
+pub mod online;
+pub mod peer_lost;
+pub mod settings;
+pub mod win;
+
+use bevy::prelude::*;
+
+use crate::{FontAssets, HOVERED_BUTTON, NORMAL_BUTTON, PRESSED_BUTTON, TEXT};
+
+#[derive(Component)]
+pub struct MenuUI;
+
+pub fn spawn_button(
+    parent: &mut ChildBuilder,
+    fonts: &FontAssets,
+    text: &str,
+    button: impl Component,
+) {
+    parent
+        .spawn_bundle(ButtonBundle {
+            style: Style {
+                size: Size::new(Val::Px(200.), Val::Px(65.)),
+                margin: UiRect::all(Val::Px(10.)),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            color: NORMAL_BUTTON.into(),
+            ..default()
+        })
+        .insert(button)
+        .with_children(|parent| {
+            parent.spawn_bundle(TextBundle::from_section(
+                text,
+                TextStyle {
+                    font: fonts.default_font.clone(),
+                    font_size: 30.,
+                    color: TEXT,
+                },
+            ));
+        });
+}
+
+pub fn btn_visuals_system(
+    mut query: Query<(&Interaction, &mut UiColor), (Changed<Interaction>, With<Button>)>,
+) {
+    for (interaction, mut color) in query.iter_mut() {
+        *color = match interaction {
+            Interaction::Clicked => PRESSED_BUTTON.into(),
+            Interaction::Hovered => HOVERED_BUTTON.into(),
+            Interaction::None => NORMAL_BUTTON.into(),
+        };
+    }
+}
@@ -0,0 +1,66 @@
+use bevy::prelude::*;
+
+use crate::{
+    menu::{connect::LastRoom, spawn_button},
+    AppState, FontAssets,
+};
+
+use super::MenuUI;
+
+#[derive(Component)]
+enum OnlineButton {
+    Connect,
+    Back,
+}
+
+pub fn setup_ui(mut commands: Commands, fonts: Res<FontAssets>) {
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.), Val::Percent(100.)),
+                flex_direction: FlexDirection::ColumnReverse,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            color: Color::NONE.into(),
+            ..default()
+        })
+        .insert(MenuUI)
+        .with_children(|parent| {
+            spawn_button(parent, &fonts, "Connect", OnlineButton::Connect);
+            spawn_button(parent, &fonts, "Back", OnlineButton::Back);
+        });
+}
+
+pub fn btn_visuals(
+    query: Query<(&Interaction, &mut UiColor), (Changed<Interaction>, With<Button>)>,
+) {
+    super::btn_visuals_system(query);
+}
+
+pub fn btn_listeners(
+    mut state: ResMut<State<AppState>>,
+    mut last_room: ResMut<LastRoom>,
+    query: Query<(&Interaction, &OnlineButton), Changed<Interaction>>,
+) {
+    for (interaction, button) in query.iter() {
+        if *interaction == Interaction::Clicked {
+            match button {
+                OnlineButton::Connect => {
+                    // a fresh connect builds its room URL from the current
+                    // settings; only a rematch reuses `LastRoom`
+                    last_room.0 = None;
+                    state.set(AppState::MenuConnect).unwrap()
+                }
+                OnlineButton::Back => state.set(AppState::MenuMain).unwrap(),
+            }
+        }
+    }
+}
+
+pub fn cleanup_ui(query: Query<Entity, With<MenuUI>>, mut commands: Commands) {
+    for e in query.iter() {
+        commands.entity(e).despawn_recursive();
+    }
+}
@@ -0,0 +1,70 @@
+use bevy::prelude::*;
+
+use crate::{menu::spawn_button, AppState, FontAssets};
+
+use super::MenuUI;
+
+#[derive(Component)]
+struct MainMenuButton;
+
+#[derive(Component)]
+struct RematchButton;
+
+pub fn setup_ui(mut commands: Commands, fonts: Res<FontAssets>) {
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.), Val::Percent(100.)),
+                flex_direction: FlexDirection::ColumnReverse,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            color: Color::NONE.into(),
+            ..default()
+        })
+        .insert(MenuUI)
+        .with_children(|parent| {
+            parent.spawn_bundle(TextBundle::from_section(
+                "Peer disconnected",
+                TextStyle {
+                    font: fonts.default_font.clone(),
+                    font_size: 40.,
+                    color: crate::TEXT,
+                },
+            ));
+            spawn_button(parent, &fonts, "Rematch", RematchButton);
+            spawn_button(parent, &fonts, "Main Menu", MainMenuButton);
+        });
+}
+
+pub fn btn_visuals(
+    query: Query<(&Interaction, &mut UiColor), (Changed<Interaction>, With<Button>)>,
+) {
+    super::btn_visuals_system(query);
+}
+
+pub fn btn_listeners(
+    mut state: ResMut<State<AppState>>,
+    rematch_query: Query<&Interaction, (Changed<Interaction>, With<RematchButton>)>,
+    main_menu_query: Query<&Interaction, (Changed<Interaction>, With<MainMenuButton>)>,
+) {
+    for interaction in rematch_query.iter() {
+        if *interaction == Interaction::Clicked {
+            // Re-entering MenuConnect re-runs `create_matchbox_socket`, which
+            // reuses `connect::LastRoom` to rejoin the same room id.
+            state.set(AppState::MenuConnect).unwrap();
+        }
+    }
+    for interaction in main_menu_query.iter() {
+        if *interaction == Interaction::Clicked {
+            state.set(AppState::MenuMain).unwrap();
+        }
+    }
+}
+
+pub fn cleanup_ui(query: Query<Entity, With<MenuUI>>, mut commands: Commands) {
+    for e in query.iter() {
+        commands.entity(e).despawn_recursive();
+    }
+}
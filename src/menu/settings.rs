@@ -0,0 +1,147 @@
+use bevy::prelude::*;
+
+use crate::{menu::spawn_button, settings::SessionSettings, AppState, FontAssets};
+
+use super::MenuUI;
+
+#[derive(Component, Clone, Copy)]
+enum Field {
+    Fps,
+    MaxPrediction,
+    InputDelay,
+    CheckDistance,
+    NumPlayers,
+}
+
+impl Field {
+    fn label(self, settings: &SessionSettings) -> String {
+        match self {
+            Field::Fps => format!("fps: {}", settings.fps),
+            Field::MaxPrediction => format!("max prediction: {}", settings.max_prediction),
+            Field::InputDelay => format!("input delay: {}", settings.input_delay),
+            Field::CheckDistance => format!("check distance: {}", settings.check_distance),
+            Field::NumPlayers => format!("players: {}", settings.num_players),
+        }
+    }
+}
+
+#[derive(Component)]
+enum SettingsButton {
+    Dec(Field),
+    Inc(Field),
+    Back,
+}
+
+fn spawn_row(parent: &mut ChildBuilder, fonts: &FontAssets, settings: &SessionSettings, field: Field) {
+    parent
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                flex_direction: FlexDirection::Row,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            color: Color::NONE.into(),
+            ..default()
+        })
+        .with_children(|row| {
+            spawn_button(row, fonts, "-", SettingsButton::Dec(field));
+            row.spawn_bundle(TextBundle::from_section(
+                field.label(settings),
+                TextStyle {
+                    font: fonts.default_font.clone(),
+                    font_size: 25.,
+                    color: crate::TEXT,
+                },
+            ))
+            .insert(field);
+            spawn_button(row, fonts, "+", SettingsButton::Inc(field));
+        });
+}
+
+pub fn setup_ui(mut commands: Commands, fonts: Res<FontAssets>, settings: Res<SessionSettings>) {
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.), Val::Percent(100.)),
+                flex_direction: FlexDirection::ColumnReverse,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            color: Color::NONE.into(),
+            ..default()
+        })
+        .insert(MenuUI)
+        .with_children(|parent| {
+            spawn_row(parent, &fonts, &settings, Field::Fps);
+            spawn_row(parent, &fonts, &settings, Field::MaxPrediction);
+            spawn_row(parent, &fonts, &settings, Field::InputDelay);
+            spawn_row(parent, &fonts, &settings, Field::CheckDistance);
+            spawn_row(parent, &fonts, &settings, Field::NumPlayers);
+            parent.spawn_bundle(TextBundle::from_section(
+                "fps takes effect on next launch",
+                TextStyle {
+                    font: fonts.default_font.clone(),
+                    font_size: 18.,
+                    color: crate::TEXT,
+                },
+            ));
+            spawn_button(parent, &fonts, "Back", SettingsButton::Back);
+        });
+}
+
+pub fn btn_visuals(
+    query: Query<(&Interaction, &mut UiColor), (Changed<Interaction>, With<Button>)>,
+) {
+    super::btn_visuals_system(query);
+}
+
+pub fn btn_listeners(
+    mut settings: ResMut<SessionSettings>,
+    mut state: ResMut<State<AppState>>,
+    mut labels: Query<(&mut Text, &Field)>,
+    query: Query<(&Interaction, &SettingsButton), Changed<Interaction>>,
+) {
+    for (interaction, button) in query.iter() {
+        if *interaction != Interaction::Clicked {
+            continue;
+        }
+
+        match button {
+            SettingsButton::Inc(Field::Fps) => settings.fps += 10,
+            SettingsButton::Dec(Field::Fps) => settings.fps = settings.fps.saturating_sub(10).max(10),
+            SettingsButton::Inc(Field::MaxPrediction) => settings.max_prediction += 1,
+            SettingsButton::Dec(Field::MaxPrediction) => {
+                settings.max_prediction = settings.max_prediction.saturating_sub(1).max(1)
+            }
+            SettingsButton::Inc(Field::InputDelay) => settings.input_delay += 1,
+            SettingsButton::Dec(Field::InputDelay) => {
+                settings.input_delay = settings.input_delay.saturating_sub(1)
+            }
+            SettingsButton::Inc(Field::CheckDistance) => settings.check_distance += 1,
+            SettingsButton::Dec(Field::CheckDistance) => {
+                settings.check_distance = settings.check_distance.saturating_sub(1).max(1)
+            }
+            SettingsButton::Inc(Field::NumPlayers) => settings.num_players += 1,
+            SettingsButton::Dec(Field::NumPlayers) => {
+                settings.num_players = settings.num_players.saturating_sub(1).max(2)
+            }
+            SettingsButton::Back => {
+                settings.save();
+                state.set(AppState::MenuMain).unwrap();
+            }
+        }
+    }
+
+    for (mut text, field) in labels.iter_mut() {
+        if let Some(section) = text.sections.first_mut() {
+            section.value = field.label(&settings);
+        }
+    }
+}
+
+pub fn cleanup_ui(query: Query<Entity, With<MenuUI>>, mut commands: Commands) {
+    for e in query.iter() {
+        commands.entity(e).despawn_recursive();
+    }
+}
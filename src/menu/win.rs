@@ -0,0 +1,58 @@
+use bevy::prelude::*;
+
+use crate::{menu::spawn_button, AppState, FontAssets};
+
+use super::MenuUI;
+
+#[derive(Component)]
+struct RematchButton;
+
+pub fn setup_ui(mut commands: Commands, fonts: Res<FontAssets>) {
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.), Val::Percent(100.)),
+                flex_direction: FlexDirection::ColumnReverse,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            color: Color::NONE.into(),
+            ..default()
+        })
+        .insert(MenuUI)
+        .with_children(|parent| {
+            parent.spawn_bundle(TextBundle::from_section(
+                "You win!",
+                TextStyle {
+                    font: fonts.default_font.clone(),
+                    font_size: 60.,
+                    color: crate::TEXT,
+                },
+            ));
+            spawn_button(parent, &fonts, "Main Menu", RematchButton);
+        });
+}
+
+pub fn btn_visuals(
+    query: Query<(&Interaction, &mut UiColor), (Changed<Interaction>, With<Button>)>,
+) {
+    super::btn_visuals_system(query);
+}
+
+pub fn btn_listeners(
+    mut state: ResMut<State<AppState>>,
+    query: Query<&Interaction, (Changed<Interaction>, With<RematchButton>)>,
+) {
+    for interaction in query.iter() {
+        if *interaction == Interaction::Clicked {
+            state.set(AppState::MenuMain).unwrap();
+        }
+    }
+}
+
+pub fn cleanup_ui(query: Query<Entity, With<MenuUI>>, mut commands: Commands) {
+    for e in query.iter() {
+        commands.entity(e).despawn_recursive();
+    }
+}
@@ -0,0 +1,215 @@
+use std::fs;
+use std::io::Write;
+
+use bevy::prelude::*;
+use bevy_ggrs::{PlayerInputs, Session};
+use ggrs::PlayerHandle;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    checksum::{combined_checksum, Checksum},
+    round::{FrameCount, Input, Player, Velocity},
+    settings::SessionSettings,
+    AppState, GGRSConfig,
+};
+
+const REPLAY_PATH: &str = "replay.bin";
+
+/// Every simulated frame's inputs plus the checksum GGRS observed for it, so a
+/// later replay can both reproduce the match and prove it reproduced it exactly.
+/// Frames still unconfirmed when the round ends are kept as last predicted.
+/// Each frame's input vec is `settings.num_players` long, recorded at the time
+/// the match was played rather than assumed from the replaying session's
+/// current settings.
+#[derive(Default, Serialize, Deserialize)]
+pub struct ReplayLog {
+    pub seed: u64,
+    pub start_checksum: u64,
+    pub inputs: Vec<Vec<Input>>,
+    pub checksums: Vec<u64>,
+}
+
+#[derive(Default)]
+pub struct ReplayCursor {
+    pub frame: usize,
+}
+
+/// Appended after `checksum_players` in the `CHECKSUM_UPDATE` stage, so it
+/// observes every simulated frame exactly once per (re)simulation. A frame
+/// that isn't mispredicted is simulated once, well before GGRS ever reports
+/// it confirmed, so recording can't wait for `confirmed_frame` without
+/// missing it; `confirmed_frame` only tells us a frame can no longer change,
+/// it's not a precondition for logging one. A resimulation instead overwrites
+/// that frame's earlier, mispredicted entry, so by the time a frame is
+/// confirmed the log already holds its final, corrected version of it.
+pub fn record_frame(
+    session: Res<Session<GGRSConfig>>,
+    frame_count: Res<FrameCount>,
+    inputs: Res<PlayerInputs<GGRSConfig>>,
+    checksums: Query<(&Player, &Checksum)>,
+    settings: Res<SessionSettings>,
+    mut log: ResMut<ReplayLog>,
+) {
+    if !matches!(session.as_ref(), Session::P2PSession(_)) {
+        return;
+    }
+    let Some(checksum) = combined_checksum(&checksums) else {
+        return;
+    };
+
+    // `increase_frame_count` already ran in `ROLLBACK_SYSTEMS`, so the n-th
+    // simulated frame sees a count of n and is logged at index n - 1
+    let index = (frame_count.frame as usize).saturating_sub(1);
+    let frame_inputs: Vec<Input> = (0..settings.num_players)
+        .map(|handle| inputs[handle].0)
+        .collect();
+
+    if index == 0 {
+        log.start_checksum = checksum;
+    }
+
+    if index < log.inputs.len() {
+        // a rollback resimulated an earlier frame: overwrite its old,
+        // mispredicted entry with the corrected one
+        log.inputs[index] = frame_inputs;
+        log.checksums[index] = checksum;
+    } else {
+        log.inputs.push(frame_inputs);
+        log.checksums.push(checksum);
+    }
+}
+
+/// `ReplayLog`/`ReplayCursor` are global resources inserted once at startup, so
+/// without this they'd still hold the previous match's frames when a new
+/// online round starts — mirrors how `setup_round` resets `FrameCount`.
+pub fn reset_replay_log(mut commands: Commands) {
+    commands.insert_resource(ReplayLog::default());
+    commands.insert_resource(ReplayCursor::default());
+}
+
+pub fn save_replay_log(log: Res<ReplayLog>) {
+    if log.inputs.is_empty() {
+        return;
+    }
+    match bincode::serialize(log.as_ref()) {
+        Ok(bytes) => write_replay_bytes(&bytes),
+        Err(err) => error!("failed to serialize replay log: {err}"),
+    }
+}
+
+pub fn load_replay_log(mut commands: Commands, mut state: ResMut<State<AppState>>) {
+    match read_replay_bytes().and_then(|bytes| bincode::deserialize::<ReplayLog>(&bytes).ok()) {
+        Some(log) => {
+            commands.insert_resource(log);
+            commands.insert_resource(ReplayCursor::default());
+        }
+        None => {
+            error!("no replay log found at {REPLAY_PATH}, returning to main menu");
+            let _ = state.set(AppState::MenuMain);
+        }
+    }
+}
+
+/// Drop-in replacement for `apply_inputs` while replaying: velocity comes from
+/// the recorded log instead of `PlayerInputs`, but the integration is identical
+/// so the rest of the rollback schedule doesn't need to know it's replaying.
+pub fn replay_apply_inputs(
+    mut query: Query<(&mut Velocity, &Player)>,
+    log: Res<ReplayLog>,
+    cursor: Res<ReplayCursor>,
+) {
+    const INPUT_UP: u8 = 1 << 0;
+    const INPUT_DOWN: u8 = 1 << 1;
+    const INPUT_LEFT: u8 = 1 << 2;
+    const INPUT_RIGHT: u8 = 1 << 3;
+    const MOVE_SPEED: f32 = 700.;
+
+    let Some(frame_inputs) = log.inputs.get(cursor.frame) else {
+        return;
+    };
+
+    for (mut v, p) in query.iter_mut() {
+        let input = frame_inputs[p.handle as PlayerHandle];
+
+        let mut direction = Vec2::ZERO;
+        if input.inp & INPUT_UP != 0 {
+            direction.y += 1.;
+        }
+        if input.inp & INPUT_DOWN != 0 {
+            direction.y -= 1.;
+        }
+        if input.inp & INPUT_LEFT != 0 {
+            direction.x -= 1.;
+        }
+        if input.inp & INPUT_RIGHT != 0 {
+            direction.x += 1.;
+        }
+
+        if direction == Vec2::ZERO {
+            continue;
+        }
+
+        v.0 += direction.normalize() * MOVE_SPEED * bevy_ggrs::ggrs_time_step();
+    }
+}
+
+/// Verification mode: compare the checksum the replay just produced against the
+/// one recorded live, so a regression in rollback determinism shows up here
+/// instead of silently as an unreproducible online desync.
+pub fn verify_replay_checksum(
+    log: Res<ReplayLog>,
+    cursor: Res<ReplayCursor>,
+    checksums: Query<(&Player, &Checksum)>,
+) {
+    let Some(&expected) = log.checksums.get(cursor.frame) else {
+        return;
+    };
+    let Some(actual) = combined_checksum(&checksums) else {
+        return;
+    };
+
+    if actual != expected {
+        error!(
+            "replay desync at frame {}: expected checksum {expected}, got {actual}",
+            cursor.frame
+        );
+    }
+}
+
+pub fn advance_or_finish_replay(
+    mut cursor: ResMut<ReplayCursor>,
+    log: Res<ReplayLog>,
+    mut state: ResMut<State<AppState>>,
+) {
+    cursor.frame += 1;
+    if cursor.frame >= log.inputs.len() {
+        let _ = state.set(AppState::MenuMain);
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn write_replay_bytes(bytes: &[u8]) {
+    if let Err(err) = fs::File::create(REPLAY_PATH).and_then(|mut f| f.write_all(bytes)) {
+        error!("failed to write replay log to {REPLAY_PATH}: {err}");
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn read_replay_bytes() -> Option<Vec<u8>> {
+    fs::read(REPLAY_PATH).ok()
+}
+
+#[cfg(target_arch = "wasm32")]
+fn write_replay_bytes(bytes: &[u8]) {
+    let encoded = base64::encode(bytes);
+    if let Some(storage) = web_sys::window().and_then(|w| w.local_storage().ok().flatten()) {
+        let _ = storage.set_item("replay", &encoded);
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn read_replay_bytes() -> Option<Vec<u8>> {
+    let storage = web_sys::window()?.local_storage().ok()??;
+    let encoded = storage.get_item("replay").ok()??;
+    base64::decode(encoded).ok()
+}
@@ -0,0 +1,179 @@
+use bevy::prelude::*;
+use bevy_ggrs::{PlayerInputs, Rollback, RollbackIdProvider, Session};
+use bytemuck::{Pod, Zeroable};
+use ggrs::{InputStatus, P2PSession, PlayerHandle};
+
+use crate::{
+    checksum::Checksum, settings::SessionSettings, AppState, FontAssets, GGRSConfig, ImageAssets,
+};
+
+const INPUT_UP: u8 = 1 << 0;
+const INPUT_DOWN: u8 = 1 << 1;
+const INPUT_LEFT: u8 = 1 << 2;
+const INPUT_RIGHT: u8 = 1 << 3;
+
+const MOVE_SPEED: f32 = 700.;
+const MAX_SPEED: f32 = 1000.;
+const FRICTION: f32 = 1300.;
+const PLAYER_RADIUS: f32 = 25.;
+const WIN_WIDTH: f32 = 600.;
+
+#[repr(C)]
+#[derive(Copy, Clone, PartialEq, Eq, Pod, Zeroable, serde::Serialize, serde::Deserialize)]
+pub struct Input {
+    pub inp: u8,
+}
+
+#[derive(Default, Reflect, Hash, Component)]
+#[reflect(Hash)]
+pub struct FrameCount {
+    pub frame: u32,
+}
+
+#[derive(Default, Reflect, Component)]
+pub struct Velocity(pub Vec2);
+
+#[derive(Component)]
+pub struct Player {
+    pub handle: usize,
+}
+
+/// Reads local keyboard state into the bitpacked `Input` GGRS sends over the wire.
+pub fn input(_handle: In<PlayerHandle>, keyboard_input: Res<Input<KeyCode>>) -> Input {
+    let mut inp: u8 = 0;
+
+    if keyboard_input.pressed(KeyCode::W) || keyboard_input.pressed(KeyCode::Up) {
+        inp |= INPUT_UP;
+    }
+    if keyboard_input.pressed(KeyCode::S) || keyboard_input.pressed(KeyCode::Down) {
+        inp |= INPUT_DOWN;
+    }
+    if keyboard_input.pressed(KeyCode::A) || keyboard_input.pressed(KeyCode::Left) {
+        inp |= INPUT_LEFT;
+    }
+    if keyboard_input.pressed(KeyCode::D) || keyboard_input.pressed(KeyCode::Right) {
+        inp |= INPUT_RIGHT;
+    }
+
+    Input { inp }
+}
+
+pub fn setup_round(mut commands: Commands) {
+    commands.insert_resource(FrameCount { frame: 0 });
+}
+
+/// Spawns `settings.num_players` rollback-synced player handles for a round.
+/// This is shared by local, online and spectator rounds alike: a spectator runs
+/// the same schedule over the same entities, it just never supplies local input
+/// for them.
+pub fn spawn_players(
+    mut commands: Commands,
+    mut rip: ResMut<RollbackIdProvider>,
+    images: Res<ImageAssets>,
+    settings: Res<SessionSettings>,
+) {
+    for handle in 0..settings.num_players {
+        let angle = handle as f32 / settings.num_players as f32 * std::f32::consts::TAU;
+        let pos = Vec3::new(angle.cos(), angle.sin(), 0.) * 150.;
+
+        commands
+            .spawn_bundle(SpriteBundle {
+                transform: Transform::from_translation(pos),
+                texture: images.ggrs_logo.clone(),
+                ..default()
+            })
+            .insert(Player { handle })
+            .insert(Velocity::default())
+            .insert(Checksum::default())
+            .insert(Rollback::new(rip.next_id()));
+    }
+}
+
+pub fn apply_inputs(
+    mut query: Query<(&mut Velocity, &Player)>,
+    inputs: Res<PlayerInputs<GGRSConfig>>,
+) {
+    for (mut v, p) in query.iter_mut() {
+        let (input, status) = inputs[p.handle];
+        if matches!(status, InputStatus::Disconnected) {
+            continue;
+        }
+
+        let mut direction = Vec2::ZERO;
+        if input.inp & INPUT_UP != 0 {
+            direction.y += 1.;
+        }
+        if input.inp & INPUT_DOWN != 0 {
+            direction.y -= 1.;
+        }
+        if input.inp & INPUT_LEFT != 0 {
+            direction.x -= 1.;
+        }
+        if input.inp & INPUT_RIGHT != 0 {
+            direction.x += 1.;
+        }
+
+        if direction == Vec2::ZERO {
+            continue;
+        }
+
+        v.0 += direction.normalize() * MOVE_SPEED * bevy_ggrs::ggrs_time_step();
+    }
+}
+
+pub fn update_velocity(time: Res<Time>, mut query: Query<&mut Velocity>) {
+    let dt = time.delta_seconds();
+    for mut v in query.iter_mut() {
+        let speed = v.0.length();
+        if speed > 0. {
+            let drop = (FRICTION * dt).min(speed);
+            v.0 *= (speed - drop) / speed;
+        }
+        if v.0.length() > MAX_SPEED {
+            v.0 = v.0.normalize() * MAX_SPEED;
+        }
+    }
+}
+
+pub fn move_players(mut query: Query<(&mut Transform, &Velocity)>, time: Res<Time>) {
+    let dt = time.delta_seconds();
+    for (mut t, v) in query.iter_mut() {
+        t.translation += (v.0 * dt).extend(0.);
+    }
+}
+
+pub fn increase_frame_count(mut frame_count: ResMut<FrameCount>) {
+    frame_count.frame += 1;
+}
+
+pub fn check_win(query: Query<&Transform, With<Player>>, mut state: ResMut<State<AppState>>) {
+    for t in query.iter() {
+        if t.translation.length() > WIN_WIDTH {
+            let _ = state.set(AppState::Win);
+            break;
+        }
+    }
+}
+
+pub fn print_p2p_events(mut session: ResMut<Session<GGRSConfig>>) {
+    match session.as_mut() {
+        Session::P2PSession(session) => {
+            for event in session.events() {
+                info!("GGRS event: {:?}", event);
+            }
+        }
+        Session::SpectatorSession(session) => {
+            for event in session.events() {
+                info!("GGRS spectator event: {:?}", event);
+            }
+        }
+        Session::SyncTestSession(_) => {}
+    }
+}
+
+pub fn cleanup_round(query: Query<Entity, With<Player>>, mut commands: Commands) {
+    for e in query.iter() {
+        commands.entity(e).despawn_recursive();
+    }
+    commands.remove_resource::<FrameCount>();
+}
@@ -0,0 +1,75 @@
+use std::fs;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+const SETTINGS_PATH: &str = "settings.json";
+
+/// Netcode tuning knobs that used to be compile-time constants. Everything
+/// here except `fps` is consumed at match-start time (`create_matchbox_socket`
+/// / `SessionBuilder`) and so takes effect immediately; `fps` feeds
+/// `GGRSPlugin::with_update_frequency`, which can only be set once while
+/// building the app, so a changed fps value takes effect on the next launch.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SessionSettings {
+    pub fps: usize,
+    pub max_prediction: usize,
+    pub input_delay: usize,
+    pub check_distance: usize,
+    pub num_players: usize,
+}
+
+impl Default for SessionSettings {
+    fn default() -> Self {
+        Self {
+            fps: 60,
+            max_prediction: 12,
+            input_delay: 2,
+            check_distance: 2,
+            num_players: 2,
+        }
+    }
+}
+
+impl SessionSettings {
+    pub fn load() -> Self {
+        read_bytes()
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) {
+        match serde_json::to_vec_pretty(self) {
+            Ok(bytes) => write_bytes(&bytes),
+            Err(err) => error!("failed to serialize session settings: {err}"),
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn read_bytes() -> Option<Vec<u8>> {
+    fs::read(SETTINGS_PATH).ok()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn write_bytes(bytes: &[u8]) {
+    if let Err(err) = fs::write(SETTINGS_PATH, bytes) {
+        error!("failed to write session settings to {SETTINGS_PATH}: {err}");
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn read_bytes() -> Option<Vec<u8>> {
+    let storage = web_sys::window()?.local_storage().ok()??;
+    storage.get_item("settings").ok()?.map(String::into_bytes)
+}
+
+#[cfg(target_arch = "wasm32")]
+fn write_bytes(bytes: &[u8]) {
+    let Ok(json) = std::str::from_utf8(bytes) else {
+        return;
+    };
+    if let Some(storage) = web_sys::window().and_then(|w| w.local_storage().ok().flatten()) {
+        let _ = storage.set_item("settings", json);
+    }
+}